@@ -23,10 +23,6 @@ use crate::{
         HashOutput,
     },
     topics::Topics,
-    types::{
-        RentParams,
-        RentStatus,
-    },
     Environment,
     Result,
 };
@@ -60,6 +56,97 @@ impl ReturnFlags {
     }
 }
 
+/// The flags used to change the behavior of a contract call.
+#[derive(Clone, Copy, Default)]
+pub struct CallFlags {
+    value: u32,
+}
+
+impl CallFlags {
+    /// Bit to forward the input of the current contract to the callee.
+    ///
+    /// Also consumes the input, so it cannot be used again afterwards.
+    const FORWARD_INPUT: u32 = 0b0000_0001;
+
+    /// Identical to [`Self::FORWARD_INPUT`] but does not consume the input.
+    const CLONE_INPUT: u32 = 0b0000_0010;
+
+    /// Bit to indicate that the callee's return data should be directly returned
+    /// to the caller of this contract, rather than resuming execution.
+    const TAIL_CALL: u32 = 0b0000_0100;
+
+    /// Bit to allow the callee to reenter into the caller.
+    ///
+    /// By default the callee is denied from calling back into the caller
+    /// in order to guard against reentrancy bugs.
+    const ALLOW_REENTRY: u32 = 0b0000_1000;
+
+    /// Sets the bit to forward the input of the current contract to the callee.
+    ///
+    /// # Note
+    ///
+    /// The input is consumed and cannot be used again for anything else
+    /// once this flag is set.
+    pub fn set_forward_input(mut self, forward_input: bool) -> Self {
+        match forward_input {
+            true => self.value |= Self::FORWARD_INPUT,
+            false => self.value &= !Self::FORWARD_INPUT,
+        }
+        self
+    }
+
+    /// Sets the bit to clone the input of the current contract and forward
+    /// it to the callee.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Self::set_forward_input`] this leaves the input of the
+    /// current contract intact so that it can be used again afterwards.
+    pub fn set_clone_input(mut self, clone_input: bool) -> Self {
+        match clone_input {
+            true => self.value |= Self::CLONE_INPUT,
+            false => self.value &= !Self::CLONE_INPUT,
+        }
+        self
+    }
+
+    /// Sets the bit to make the call a tail call.
+    ///
+    /// # Note
+    ///
+    /// On success the callee's return data is returned directly as this
+    /// contract's own return value instead of resuming execution of this
+    /// contract.
+    pub fn set_tail_call(mut self, tail_call: bool) -> Self {
+        match tail_call {
+            true => self.value |= Self::TAIL_CALL,
+            false => self.value &= !Self::TAIL_CALL,
+        }
+        self
+    }
+
+    /// Sets the bit to allow the callee to reenter into the caller.
+    ///
+    /// # Note
+    ///
+    /// Reentrancy is denied by default as a guard against reentrancy bugs.
+    /// Setting this flag explicitly opts into allowing the callee to call
+    /// back into this contract.
+    pub fn set_allow_reentry(mut self, allow_reentry: bool) -> Self {
+        match allow_reentry {
+            true => self.value |= Self::ALLOW_REENTRY,
+            false => self.value &= !Self::ALLOW_REENTRY,
+        }
+        self
+    }
+
+    /// Returns the underlying `u32` representation.
+    #[cfg(not(feature = "ink-experimental-engine"))]
+    pub(crate) fn into_u32(self) -> u32 {
+        self.value
+    }
+}
+
 /// Environmental contract functionality that does not require `Environment`.
 pub trait EnvBackend {
     /// Writes the value to the contract storage under the given key.
@@ -120,8 +207,26 @@ pub trait EnvBackend {
         R: scale::Encode;
 
     /// Prints the given contents to the console log.
+    #[deprecated(since = "3.2.0", note = "please use `debug_message` instead")]
     fn println(&mut self, content: &str);
 
+    /// Emits a debug message to the node's log.
+    ///
+    /// # Note
+    ///
+    /// Recording debug messages can be turned off on the node, in which case
+    /// the message is discarded. The `content` is already formatted by the
+    /// time this is called, so that cost is paid either way. Callers that build expensive
+    /// messages (e.g. via `format!`) should check the result once and skip
+    /// that work if logging is disabled instead of calling this on every
+    /// message.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::LoggingDisabled`][`crate::Error::LoggingDisabled`] if the
+    ///   node has debug message recording turned off.
+    fn debug_message(&mut self, content: &str) -> Result<()>;
+
     /// Conducts the crypto hash of the given input and stores the result in `output`.
     fn hash_bytes<H>(&mut self, input: &[u8], output: &mut <H as HashOutput>::Type)
     where
@@ -219,30 +324,6 @@ pub trait TypedEnvBackend: EnvBackend {
     /// For more details visit: [`balance`][`crate::balance`]
     fn balance<T: Environment>(&mut self) -> Result<T::Balance>;
 
-    /// Returns the current rent allowance for the executed contract.
-    ///
-    /// # Note
-    ///
-    /// For more details visit: [`rent_allowance`][`crate::rent_allowance`]
-    fn rent_allowance<T: Environment>(&mut self) -> Result<T::Balance>;
-
-    /// Returns information needed for rent calculations.
-    ///
-    /// # Note
-    ///
-    /// For more details visit: [`RentParams`][`crate::RentParams`]
-    fn rent_params<T: Environment>(&mut self) -> Result<RentParams<T>>;
-
-    /// Returns information about the required deposit and resulting rent.
-    ///
-    /// # Note
-    ///
-    /// For more details visit: [`RentStatus`][`crate::RentStatus`]
-    fn rent_status<T: Environment>(
-        &mut self,
-        at_refcount: Option<core::num::NonZeroU32>,
-    ) -> Result<RentStatus<T>>;
-
     /// Returns the current block number.
     ///
     /// # Note
@@ -257,13 +338,6 @@ pub trait TypedEnvBackend: EnvBackend {
     /// For more details visit: [`minimum_balance`][`crate::minimum_balance`]
     fn minimum_balance<T: Environment>(&mut self) -> Result<T::Balance>;
 
-    /// Returns the tombstone deposit of the contract chain.
-    ///
-    /// # Note
-    ///
-    /// For more details visit: [`tombstone_deposit`][`crate::tombstone_deposit`]
-    fn tombstone_deposit<T: Environment>(&mut self) -> Result<T::Balance>;
-
     /// Emits an event with the given event data.
     ///
     /// # Note
@@ -274,18 +348,17 @@ pub trait TypedEnvBackend: EnvBackend {
         T: Environment,
         Event: Topics + scale::Encode;
 
-    /// Sets the rent allowance of the executed contract to the new value.
+    /// Invokes a contract message.
     ///
     /// # Note
     ///
-    /// For more details visit: [`set_rent_allowance`][`crate::set_rent_allowance`]
-    fn set_rent_allowance<T>(&mut self, new_value: T::Balance)
-    where
-        T: Environment;
-
-    /// Invokes a contract message.
+    /// The `call_data`'s [`CallFlags`] control the behavior of the call, e.g.
+    /// forwarding this contract's own input to the callee, turning the call
+    /// into a tail call, or allowing the callee to reenter this contract.
     ///
-    /// # Note
+    /// The `call_data`'s `storage_deposit_limit` caps how much balance the
+    /// callee is allowed to lock for newly created storage; exceeding it
+    /// surfaces as [`Error::StorageDepositLimitExhausted`][`crate::Error::StorageDepositLimitExhausted`].
     ///
     /// For more details visit: [`invoke_contract`][`crate::invoke_contract`]
     fn invoke_contract<T, Args>(
@@ -300,6 +373,14 @@ pub trait TypedEnvBackend: EnvBackend {
     ///
     /// # Note
     ///
+    /// The `call_data`'s [`CallFlags`] control the behavior of the call, e.g.
+    /// forwarding this contract's own input to the callee, turning the call
+    /// into a tail call, or allowing the callee to reenter this contract.
+    ///
+    /// The `call_data`'s `storage_deposit_limit` caps how much balance the
+    /// callee is allowed to lock for newly created storage; exceeding it
+    /// surfaces as [`Error::StorageDepositLimitExhausted`][`crate::Error::StorageDepositLimitExhausted`].
+    ///
     /// For more details visit: [`eval_contract`][`crate::eval_contract`]
     fn eval_contract<T, Args, R>(
         &mut self,
@@ -310,10 +391,57 @@ pub trait TypedEnvBackend: EnvBackend {
         Args: scale::Encode,
         R: scale::Decode;
 
+    /// Invokes the code at `call_data`'s `code_hash` as a delegate call.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Self::invoke_contract`], the invoked code runs against the
+    /// storage, balance, and `account_id` of the *caller* rather than of
+    /// the contract the code hash was uploaded by. Combined with
+    /// [`Self::set_code_hash`], this enables the library/proxy pattern
+    /// where shared logic stored once under one code hash is executed by
+    /// many contracts against their own state.
+    ///
+    /// For more details visit: [`invoke_contract_delegate`][`crate::invoke_contract_delegate`]
+    fn invoke_contract_delegate<T, Args>(
+        &mut self,
+        call_data: &CallParams<T, Args, ()>,
+    ) -> Result<()>
+    where
+        T: Environment,
+        Args: scale::Encode;
+
+    /// Evaluates the code at `call_data`'s `code_hash` as a delegate call
+    /// and returns its result.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Self::eval_contract`], the invoked code runs against the
+    /// storage, balance, and `account_id` of the *caller* rather than of
+    /// the contract the code hash was uploaded by. Combined with
+    /// [`Self::set_code_hash`], this enables the library/proxy pattern
+    /// where shared logic stored once under one code hash is executed by
+    /// many contracts against their own state.
+    ///
+    /// For more details visit: [`eval_contract_delegate`][`crate::eval_contract_delegate`]
+    fn eval_contract_delegate<T, Args, R>(
+        &mut self,
+        call_data: &CallParams<T, Args, ReturnType<R>>,
+    ) -> Result<R>
+    where
+        T: Environment,
+        Args: scale::Encode,
+        R: scale::Decode;
+
     /// Instantiates another contract.
     ///
     /// # Note
     ///
+    /// The `params`' `storage_deposit_limit` caps how much balance the
+    /// instantiated contract is allowed to lock for newly created storage;
+    /// exceeding it surfaces as
+    /// [`Error::StorageDepositLimitExhausted`][`crate::Error::StorageDepositLimitExhausted`].
+    ///
     /// For more details visit: [`instantiate_contract`][`crate::instantiate_contract`]
     fn instantiate_contract<T, Args, Salt, C>(
         &mut self,
@@ -324,26 +452,31 @@ pub trait TypedEnvBackend: EnvBackend {
         Args: scale::Encode,
         Salt: AsRef<[u8]>;
 
-    /// Restores a smart contract tombstone.
+    /// Terminates a smart contract.
     ///
     /// # Note
     ///
-    /// For more details visit: [`restore_contract`][`crate::restore_contract`]
-    fn restore_contract<T>(
-        &mut self,
-        account_id: T::AccountId,
-        code_hash: T::Hash,
-        rent_allowance: T::Balance,
-        filtered_keys: &[Key],
-    ) where
+    /// For more details visit: [`terminate_contract`][`crate::terminate_contract`]
+    fn terminate_contract<T>(&mut self, beneficiary: T::AccountId) -> !
+    where
         T: Environment;
 
-    /// Terminates a smart contract.
+    /// Replaces the currently executing contract's code with the code at
+    /// the given `code_hash`.
     ///
     /// # Note
     ///
-    /// For more details visit: [`terminate_contract`][`crate::terminate_contract`]
-    fn terminate_contract<T>(&mut self, beneficiary: T::AccountId) -> !
+    /// The code is left in place for all *subsequent* calls into this
+    /// contract, while the currently executing call still runs to
+    /// completion with the old code. The contract's `AccountId` and storage
+    /// are left untouched, so this is the foundation for upgradeable and
+    /// proxy contracts.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::CodeNotFound`][`crate::Error::CodeNotFound`] if no code
+    ///   was uploaded under `code_hash`.
+    fn set_code_hash<T>(&mut self, code_hash: &T::Hash) -> Result<()>
     where
         T: Environment;
 
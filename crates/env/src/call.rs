@@ -0,0 +1,259 @@
+// Copyright 2018-2021 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Data structures to operate on contract calls.
+
+use crate::{
+    backend::CallFlags,
+    Environment,
+};
+use core::marker::PhantomData;
+
+/// Utility types for the call builders.
+pub mod utils {
+    use core::marker::PhantomData;
+
+    /// Represents a return type for contract calls.
+    ///
+    /// Only used as marker type for the `R` generic parameter of
+    /// [`super::CallParams`] and never constructed.
+    pub struct ReturnType<T>(PhantomData<fn() -> T>);
+}
+
+/// The input data and selector for a contract call or instantiation.
+pub struct ExecutionInput<Args> {
+    args: Args,
+}
+
+impl<Args> ExecutionInput<Args> {
+    /// Creates a new execution input with the given arguments.
+    pub fn new(args: Args) -> Self {
+        Self { args }
+    }
+
+    /// Returns the arguments of the execution input.
+    pub fn args(&self) -> &Args {
+        &self.args
+    }
+}
+
+/// Whether a call runs in the callee's own context or in the caller's context.
+pub enum CallType<T>
+where
+    T: Environment,
+{
+    /// A regular call: the callee executes against its own `AccountId`,
+    /// storage, and balance.
+    Call {
+        /// The address of the callee.
+        callee: T::AccountId,
+    },
+    /// A delegate call: the code stored under `code_hash` executes against
+    /// the *caller's* `AccountId`, storage, and balance.
+    DelegateCall {
+        /// The code hash of the contract whose code is executed.
+        code_hash: T::Hash,
+    },
+}
+
+/// Parameters to invoke or evaluate a contract message.
+pub struct CallParams<T, Args, R>
+where
+    T: Environment,
+{
+    call_type: CallType<T>,
+    call_flags: CallFlags,
+    gas_limit: u64,
+    transferred_value: T::Balance,
+    storage_deposit_limit: Option<T::Balance>,
+    exec_input: ExecutionInput<Args>,
+    return_type: PhantomData<fn() -> R>,
+}
+
+impl<T, Args, R> CallParams<T, Args, R>
+where
+    T: Environment,
+{
+    /// Returns the type of call, i.e. whether it is a regular call against
+    /// the callee's own context or a delegate call against the caller's.
+    pub fn call_type(&self) -> &CallType<T> {
+        &self.call_type
+    }
+
+    /// Returns the flags used to change the behavior of the call.
+    pub fn call_flags(&self) -> &CallFlags {
+        &self.call_flags
+    }
+
+    /// Returns the amount of gas supplied for the call.
+    pub fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    /// Returns the balance transferred as part of the call.
+    pub fn transferred_value(&self) -> &T::Balance {
+        &self.transferred_value
+    }
+
+    /// Returns the limit on the balance that the callee may lock for newly
+    /// created storage, if any.
+    pub fn storage_deposit_limit(&self) -> Option<&T::Balance> {
+        self.storage_deposit_limit.as_ref()
+    }
+
+    /// Returns the execution input.
+    pub fn exec_input(&self) -> &ExecutionInput<Args> {
+        &self.exec_input
+    }
+}
+
+/// Builds up a [`CallParams`] instance.
+pub struct CallBuilder<T, Args, R>
+where
+    T: Environment,
+{
+    params: CallParams<T, Args, R>,
+}
+
+impl<T, Args, R> CallBuilder<T, Args, R>
+where
+    T: Environment,
+{
+    /// Sets the flags used to change the behavior of the call.
+    pub fn call_flags(mut self, call_flags: CallFlags) -> Self {
+        self.params.call_flags = call_flags;
+        self
+    }
+
+    /// Sets the amount of gas supplied for the call.
+    pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.params.gas_limit = gas_limit;
+        self
+    }
+
+    /// Sets the balance to transfer as part of the call.
+    pub fn transferred_value(mut self, value: T::Balance) -> Self {
+        self.params.transferred_value = value;
+        self
+    }
+
+    /// Sets the limit on the balance the callee may lock for newly created
+    /// storage.
+    pub fn storage_deposit_limit(mut self, limit: T::Balance) -> Self {
+        self.params.storage_deposit_limit = Some(limit);
+        self
+    }
+
+    /// Finishes the building process and returns the finalized parameters.
+    pub fn params(self) -> CallParams<T, Args, R> {
+        self.params
+    }
+}
+
+/// Returns a new call builder for invoking the message of the account
+/// identified by `callee`.
+pub fn build_call<T, Args, R>(callee: T::AccountId) -> CallBuilder<T, Args, R>
+where
+    T: Environment,
+    Args: Default,
+{
+    CallBuilder {
+        params: CallParams {
+            call_type: CallType::Call { callee },
+            call_flags: CallFlags::default(),
+            gas_limit: 0,
+            transferred_value: Default::default(),
+            storage_deposit_limit: None,
+            exec_input: ExecutionInput::new(Default::default()),
+            return_type: Default::default(),
+        },
+    }
+}
+
+/// Returns a new call builder for a delegate call into the code stored
+/// under `code_hash`.
+///
+/// # Note
+///
+/// The code runs against the storage, balance, and `account_id` of the
+/// contract performing the call, not of the contract that uploaded the
+/// code. Combine with [`crate::set_code_hash`] for the classic
+/// library/proxy pattern.
+pub fn build_call_delegate<T, Args, R>(code_hash: T::Hash) -> CallBuilder<T, Args, R>
+where
+    T: Environment,
+    Args: Default,
+{
+    CallBuilder {
+        params: CallParams {
+            call_type: CallType::DelegateCall { code_hash },
+            call_flags: CallFlags::default(),
+            gas_limit: 0,
+            transferred_value: Default::default(),
+            storage_deposit_limit: None,
+            exec_input: ExecutionInput::new(Default::default()),
+            return_type: Default::default(),
+        },
+    }
+}
+
+/// Parameters to instantiate another contract.
+pub struct CreateParams<T, Args, Salt, C>
+where
+    T: Environment,
+{
+    code_hash: T::Hash,
+    gas_limit: u64,
+    endowment: T::Balance,
+    storage_deposit_limit: Option<T::Balance>,
+    exec_input: ExecutionInput<Args>,
+    salt_bytes: Salt,
+    return_type: PhantomData<fn() -> C>,
+}
+
+impl<T, Args, Salt, C> CreateParams<T, Args, Salt, C>
+where
+    T: Environment,
+{
+    /// Returns the code hash of the contract to instantiate.
+    pub fn code_hash(&self) -> &T::Hash {
+        &self.code_hash
+    }
+
+    /// Returns the amount of gas supplied for the instantiation.
+    pub fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    /// Returns the balance to transfer to the newly created contract.
+    pub fn endowment(&self) -> &T::Balance {
+        &self.endowment
+    }
+
+    /// Returns the limit on the balance the new contract may lock for its
+    /// initial storage, if any.
+    pub fn storage_deposit_limit(&self) -> Option<&T::Balance> {
+        self.storage_deposit_limit.as_ref()
+    }
+
+    /// Returns the execution input.
+    pub fn exec_input(&self) -> &ExecutionInput<Args> {
+        &self.exec_input
+    }
+
+    /// Returns the salt used to derive the new contract's `AccountId`.
+    pub fn salt_bytes(&self) -> &Salt {
+        &self.salt_bytes
+    }
+}
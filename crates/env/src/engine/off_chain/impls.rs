@@ -0,0 +1,356 @@
+// Copyright 2018-2021 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    backend::{
+        EnvBackend,
+        ReturnFlags,
+        TypedEnvBackend,
+    },
+    call::{
+        utils::ReturnType,
+        CallParams,
+        CreateParams,
+    },
+    hash::{
+        CryptoHash,
+        HashOutput,
+    },
+    topics::Topics,
+    Environment,
+    Error,
+    Result,
+};
+use ink_primitives::Key;
+use std::collections::BTreeMap;
+
+/// The off-chain engine used to run contracts without a node for testing.
+///
+/// Simulates a single contract instance's storage and the subset of node
+/// behavior (debug logging) needed to exercise the `EnvBackend`/
+/// `TypedEnvBackend` surface off-chain.
+pub struct EnvInstance {
+    /// The simulated contract storage, keyed by the raw storage key bytes.
+    storage: BTreeMap<[u8; 32], Vec<u8>>,
+    /// Code hashes that have been registered as if code had been uploaded
+    /// under them to the node's code cache. No code is stored.
+    contracts: BTreeMap<Vec<u8>, ()>,
+    /// The code hash this instance is currently executing under, if it
+    /// has been replaced via `set_code_hash`.
+    code_hash: Option<Vec<u8>>,
+    /// Debug messages recorded via `debug_message`/`println`, readable
+    /// back by tests.
+    debug_messages: Vec<String>,
+    /// Whether the simulated node has debug message recording turned on.
+    debug_message_enabled: bool,
+}
+
+impl Default for EnvInstance {
+    fn default() -> Self {
+        Self {
+            storage: BTreeMap::new(),
+            contracts: BTreeMap::new(),
+            code_hash: None,
+            debug_messages: Vec::new(),
+            // Recording is enabled by default so that off-chain tests
+            // observe `debug_message` output out of the box. `println`
+            // records regardless of this flag.
+            debug_message_enabled: true,
+        }
+    }
+}
+
+impl EnvInstance {
+    /// Returns the debug messages recorded so far.
+    ///
+    /// Used by tests to assert on contract debug output.
+    pub fn recorded_debug_messages(&self) -> &[String] {
+        &self.debug_messages
+    }
+
+    /// Registers the given code hash as if code had been uploaded under it
+    /// to the node's code cache.
+    pub fn set_code_hash_available(&mut self, code_hash: &[u8]) {
+        self.contracts.insert(code_hash.to_vec(), ());
+    }
+}
+
+impl EnvBackend for EnvInstance {
+    fn set_contract_storage<V>(&mut self, key: &Key, value: &V)
+    where
+        V: scale::Encode,
+    {
+        self.storage
+            .insert(*key.as_ref(), scale::Encode::encode(value));
+    }
+
+    fn get_contract_storage<R>(&mut self, key: &Key) -> Result<Option<R>>
+    where
+        R: scale::Decode,
+    {
+        match self.storage.get(key.as_ref()) {
+            Some(bytes) => {
+                let decoded = R::decode(&mut &bytes[..])?;
+                Ok(Some(decoded))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn clear_contract_storage(&mut self, key: &Key) {
+        self.storage.remove(key.as_ref());
+    }
+
+    fn decode_input<T>(&mut self) -> Result<T>
+    where
+        T: scale::Decode,
+    {
+        T::decode(&mut &[][..]).map_err(Into::into)
+    }
+
+    fn return_value<R>(&mut self, _flags: ReturnFlags, _return_value: &R) -> !
+    where
+        R: scale::Encode,
+    {
+        panic!("off-chain engine: `return_value` ends contract execution")
+    }
+
+    #[allow(deprecated)]
+    fn println(&mut self, content: &str) {
+        self.debug_messages.push(content.into());
+    }
+
+    fn debug_message(&mut self, content: &str) -> Result<()> {
+        if self.debug_message_enabled {
+            self.debug_messages.push(content.into());
+            Ok(())
+        } else {
+            Err(Error::LoggingDisabled)
+        }
+    }
+
+    fn hash_bytes<H>(&mut self, input: &[u8], output: &mut <H as HashOutput>::Type)
+    where
+        H: CryptoHash,
+    {
+        H::hash(input, output)
+    }
+
+    fn hash_encoded<H, T>(&mut self, input: &T, output: &mut <H as HashOutput>::Type)
+    where
+        H: CryptoHash,
+        T: scale::Encode,
+    {
+        H::hash(&scale::Encode::encode(input), output)
+    }
+
+    fn call_chain_extension<I, T, E, ErrorCode, F, D>(
+        &mut self,
+        _func_id: u32,
+        _input: &I,
+        status_to_result: F,
+        decode_to_result: D,
+    ) -> ::core::result::Result<T, E>
+    where
+        I: scale::Encode,
+        T: scale::Decode,
+        E: From<ErrorCode>,
+        F: FnOnce(u32) -> ::core::result::Result<(), ErrorCode>,
+        D: FnOnce(&[u8]) -> ::core::result::Result<T, E>,
+    {
+        status_to_result(0)?;
+        decode_to_result(&[])
+    }
+}
+
+impl TypedEnvBackend for EnvInstance {
+    fn caller<T: Environment>(&mut self) -> Result<T::AccountId> {
+        Ok(Default::default())
+    }
+
+    fn transferred_balance<T: Environment>(&mut self) -> Result<T::Balance> {
+        Ok(Default::default())
+    }
+
+    fn weight_to_fee<T: Environment>(&mut self, _gas: u64) -> Result<T::Balance> {
+        Ok(Default::default())
+    }
+
+    fn gas_left<T: Environment>(&mut self) -> Result<T::Balance> {
+        Ok(Default::default())
+    }
+
+    fn block_timestamp<T: Environment>(&mut self) -> Result<T::Timestamp> {
+        Ok(Default::default())
+    }
+
+    fn account_id<T: Environment>(&mut self) -> Result<T::AccountId> {
+        Ok(Default::default())
+    }
+
+    fn balance<T: Environment>(&mut self) -> Result<T::Balance> {
+        Ok(Default::default())
+    }
+
+    fn block_number<T: Environment>(&mut self) -> Result<T::BlockNumber> {
+        Ok(Default::default())
+    }
+
+    fn minimum_balance<T: Environment>(&mut self) -> Result<T::Balance> {
+        Ok(Default::default())
+    }
+
+    fn emit_event<T, Event>(&mut self, _event: Event)
+    where
+        T: Environment,
+        Event: Topics + scale::Encode,
+    {
+    }
+
+    fn invoke_contract<T, Args>(
+        &mut self,
+        call_data: &CallParams<T, Args, ()>,
+    ) -> Result<()>
+    where
+        T: Environment,
+        Args: scale::Encode,
+    {
+        Self::check_storage_deposit_limit::<T>(call_data.storage_deposit_limit())
+    }
+
+    fn eval_contract<T, Args, R>(
+        &mut self,
+        call_data: &CallParams<T, Args, ReturnType<R>>,
+    ) -> Result<R>
+    where
+        T: Environment,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        Self::check_storage_deposit_limit::<T>(call_data.storage_deposit_limit())?;
+        R::decode(&mut &[][..]).map_err(Into::into)
+    }
+
+    fn invoke_contract_delegate<T, Args>(
+        &mut self,
+        call_data: &CallParams<T, Args, ()>,
+    ) -> Result<()>
+    where
+        T: Environment,
+        Args: scale::Encode,
+    {
+        self.exec_delegate_call(call_data)
+    }
+
+    fn eval_contract_delegate<T, Args, R>(
+        &mut self,
+        call_data: &CallParams<T, Args, ReturnType<R>>,
+    ) -> Result<R>
+    where
+        T: Environment,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        self.exec_delegate_call(call_data)?;
+        R::decode(&mut &[][..]).map_err(Into::into)
+    }
+
+    fn instantiate_contract<T, Args, Salt, C>(
+        &mut self,
+        params: &CreateParams<T, Args, Salt, C>,
+    ) -> Result<T::AccountId>
+    where
+        T: Environment,
+        Args: scale::Encode,
+        Salt: AsRef<[u8]>,
+    {
+        Self::check_storage_deposit_limit::<T>(params.storage_deposit_limit())?;
+        Ok(Default::default())
+    }
+
+    fn terminate_contract<T>(&mut self, _beneficiary: T::AccountId) -> !
+    where
+        T: Environment,
+    {
+        panic!("off-chain engine: `terminate_contract` ends contract execution")
+    }
+
+    fn set_code_hash<T>(&mut self, code_hash: &T::Hash) -> Result<()>
+    where
+        T: Environment,
+    {
+        if !self.contracts.contains_key(code_hash.as_ref()) {
+            return Err(Error::CodeNotFound)
+        }
+        self.code_hash = Some(code_hash.as_ref().to_vec());
+        Ok(())
+    }
+
+    fn transfer<T>(&mut self, _destination: T::AccountId, _value: T::Balance) -> Result<()>
+    where
+        T: Environment,
+    {
+        Ok(())
+    }
+
+    fn random<T>(&mut self, _subject: &[u8]) -> Result<(T::Hash, T::BlockNumber)>
+    where
+        T: Environment,
+    {
+        Ok((Default::default(), Default::default()))
+    }
+}
+
+impl EnvInstance {
+    /// Resolves `call_data` as a delegate call: checks that `call_data`'s
+    /// code hash is registered and returns `Ok(())` if so. No code is
+    /// executed, so this instance's storage is left unchanged.
+    fn exec_delegate_call<T, Args, R>(
+        &mut self,
+        call_data: &CallParams<T, Args, R>,
+    ) -> Result<()>
+    where
+        T: Environment,
+        Args: scale::Encode,
+    {
+        let code_hash = match call_data.call_type() {
+            crate::call::CallType::DelegateCall { code_hash } => code_hash,
+            // A regular `Call` carries no code hash to delegate to; this
+            // is caller misuse (e.g. passing `build_call` params into a
+            // `*_delegate` method), so report it the same way as an
+            // unresolvable code hash rather than panicking.
+            crate::call::CallType::Call { .. } => return Err(Error::CodeNotFound),
+        };
+        if !self.contracts.contains_key(code_hash.as_ref()) {
+            return Err(Error::CodeNotFound)
+        }
+        // The off-chain engine holds no code to run under the hash.
+        Ok(())
+    }
+
+    /// Checks a call or instantiation against its `storage_deposit_limit`.
+    ///
+    /// The off-chain engine does not track the storage deposit a call would
+    /// actually lock, so there is nothing to compare a limit against. A
+    /// limit of `None` means "no limit" and always passes; a `Some` limit,
+    /// including a limit of zero, is accepted unconditionally for the same
+    /// reason. Real enforcement happens in the on-chain engine, which has
+    /// the storage deltas needed to evaluate the limit.
+    fn check_storage_deposit_limit<T>(_storage_deposit_limit: Option<&T::Balance>) -> Result<()>
+    where
+        T: Environment,
+    {
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright 2018-2021 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Errors that can be encountered upon environmental interaction.
+
+/// Errors that can be encountered upon environmental interaction.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Failed to decode a scale encoded value.
+    Decode(scale::Error),
+    /// The call to another contract has trapped.
+    CalleeTrapped,
+    /// The call to another contract has been reverted.
+    CalleeReverted,
+    /// The queried contract storage entry is missing.
+    KeyNotFound,
+    /// Transfer failed for other reasons. Most probably reserved bits are
+    /// missing.
+    TransferFailed,
+    /// The debug message recording is disabled on the node this contract
+    /// is executing on, so the message was never recorded.
+    LoggingDisabled,
+    /// No code could be found at the supplied code hash.
+    CodeNotFound,
+    /// The call would have caused the callee to lock more balance for
+    /// newly created storage than the caller's `storage_deposit_limit`
+    /// allows.
+    StorageDepositLimitExhausted,
+}
+
+impl From<scale::Error> for Error {
+    fn from(error: scale::Error) -> Self {
+        Error::Decode(error)
+    }
+}